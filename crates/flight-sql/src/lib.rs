@@ -0,0 +1,27 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Exposes Iceberg tables, loaded through a [`RestCatalog`](iceberg_catalog_rest::RestCatalog),
+//! over an Arrow [Flight SQL](https://arrow.apache.org/docs/format/FlightSql.html) server, so
+//! that any Flight SQL client can run SQL against them without embedding Rust.
+//!
+//! Queries are planned and executed with DataFusion, registering each referenced table as an
+//! [`IcebergTableProvider`](iceberg_datafusion::IcebergTableProvider) lazily on first use.
+
+mod service;
+
+pub use service::IcebergFlightSqlService;
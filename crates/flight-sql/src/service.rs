@@ -0,0 +1,392 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::flight_service_server::FlightServiceServer;
+use arrow_flight::sql::server::FlightSqlService;
+use arrow_flight::sql::{
+    CommandGetCatalogs, CommandGetDbSchemas, CommandGetTables, CommandStatementQuery, SqlInfo,
+    TicketStatementQuery,
+};
+use arrow_flight::{FlightDescriptor, FlightEndpoint, FlightInfo, Ticket};
+use datafusion::arrow::array::{RecordBatch, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::catalog_common::MemorySchemaProvider;
+use datafusion::common::TableReference;
+use datafusion::prelude::SessionContext;
+use futures::{Stream, TryStreamExt};
+use iceberg::{Catalog, NamespaceIdent, TableIdent};
+use iceberg_catalog_rest::{RestCatalog, RestCatalogConfig};
+use iceberg_datafusion::IcebergTableProvider;
+use prost::Message;
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+
+type TonicStream<T> = std::pin::Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// This service fronts a single [`RestCatalog`], so `GetCatalogs` always reports exactly this
+/// one catalog name.
+const CATALOG_NAME: &str = "iceberg";
+
+/// A Flight SQL [`FlightSqlService`] that serves Iceberg tables reachable through a single
+/// [`RestCatalog`], planning and executing SQL with DataFusion.
+///
+/// Each handshake creates a fresh [`SessionContext`]; tables referenced by a query are loaded
+/// from the catalog and registered as [`IcebergTableProvider`]s the first time a query in that
+/// session references them, keyed as `<namespace>.<table>` the same way the REST catalog
+/// integration tests address them (e.g. `default.types_test`).
+pub struct IcebergFlightSqlService {
+    catalog_config: RestCatalogConfig,
+    /// Per-handshake-token DataFusion sessions, so concurrent clients don't share state.
+    contexts: dashmap::DashMap<String, Arc<SessionContext>>,
+    /// In-flight statements, keyed by the handle embedded in the ticket `get_flight_info_statement`
+    /// hands back, so `do_get_statement` knows what to (re)execute. Each entry is removed once
+    /// `do_get_statement` serves it, so this doesn't grow unbounded over the server's lifetime.
+    statements: dashmap::DashMap<String, String>,
+}
+
+impl IcebergFlightSqlService {
+    /// Serves tables reachable through the catalog described by `catalog_config`.
+    pub fn new(catalog_config: RestCatalogConfig) -> Self {
+        Self {
+            catalog_config,
+            contexts: dashmap::DashMap::new(),
+            statements: dashmap::DashMap::new(),
+        }
+    }
+
+    /// Wraps `self` into a tonic [`FlightServiceServer`] ready to be served with
+    /// [`tonic::transport::Server`].
+    pub fn into_server(self) -> FlightServiceServer<Self> {
+        FlightServiceServer::new(self)
+    }
+
+    fn rest_catalog(&self) -> RestCatalog {
+        RestCatalog::new(self.catalog_config.clone())
+    }
+
+    /// Lists every namespace known to the catalog, joining multi-level namespaces with `.`.
+    async fn list_namespaces(&self) -> Result<Vec<String>, Status> {
+        let catalog = self.rest_catalog();
+        let namespaces = catalog.list_namespaces(None).await.map_err(to_status)?;
+        Ok(namespaces
+            .iter()
+            .map(|namespace| namespace.join("."))
+            .collect())
+    }
+
+    /// Lists every table in `namespace`, as `(namespace, table_name)` pairs.
+    async fn list_tables(&self, namespace: &str) -> Result<Vec<(String, String)>, Status> {
+        let catalog = self.rest_catalog();
+        let ident = NamespaceIdent::from_strs(namespace.split('.')).map_err(to_status)?;
+        let tables = catalog.list_tables(&ident).await.map_err(to_status)?;
+        Ok(tables
+            .into_iter()
+            .map(|table| (namespace.to_string(), table.name().to_string()))
+            .collect())
+    }
+
+    fn session_for(&self, token: &str) -> Arc<SessionContext> {
+        self.contexts
+            .entry(token.to_string())
+            .or_insert_with(|| Arc::new(SessionContext::new()))
+            .clone()
+    }
+
+    /// Loads `namespace.table` from the catalog and registers it into `ctx`, unless it is
+    /// already registered.
+    async fn ensure_table_registered(
+        &self,
+        ctx: &SessionContext,
+        namespace: &str,
+        table: &str,
+    ) -> Result<(), Status> {
+        let reference = TableReference::partial(namespace, table);
+        if ctx.table_exist(reference.clone()).unwrap_or(false) {
+            return Ok(());
+        }
+        let catalog = self.rest_catalog();
+        let ident = TableIdent::new(
+            NamespaceIdent::from_strs([namespace]).map_err(to_status)?,
+            table.to_string(),
+        );
+        let iceberg_table = catalog.load_table(&ident).await.map_err(to_status)?;
+        let provider = Arc::new(
+            IcebergTableProvider::try_new_from_table(iceberg_table)
+                .await
+                .map_err(to_status)?,
+        );
+        // The namespace becomes a schema of the session's default catalog, so that
+        // `<namespace>.<table>` resolves to the provider registered below.
+        let default_catalog = ctx.state().config_options().catalog.default_catalog.clone();
+        let df_catalog = ctx
+            .catalog(&default_catalog)
+            .ok_or_else(|| Status::internal("session has no default catalog"))?;
+        if df_catalog.schema(namespace).is_none() {
+            df_catalog
+                .register_schema(namespace, Arc::new(MemorySchemaProvider::new()))
+                .map_err(to_status)?;
+        }
+        ctx.register_table(reference, provider).map_err(to_status)?;
+        Ok(())
+    }
+
+    /// Registers every `namespace.table` reference found in `query` into `ctx` before planning
+    /// it, so ad hoc `SELECT ... FROM default.types_test` statements resolve without the client
+    /// having to register tables itself.
+    ///
+    /// References are taken from the parsed statement's resolved [`TableReference`]s rather than
+    /// scanning the raw SQL text for `.`-separated words, so literals like `1.5` in a `WHERE`
+    /// clause are never mistaken for a table reference.
+    async fn register_referenced_tables(
+        &self,
+        ctx: &SessionContext,
+        query: &str,
+    ) -> Result<(), Status> {
+        let state = ctx.state();
+        let statement = state.sql_to_statement(query, "generic").map_err(to_status)?;
+        let references = state.resolve_table_references(&statement).map_err(to_status)?;
+        for reference in references {
+            if let Some(namespace) = reference.schema() {
+                self.ensure_table_registered(ctx, namespace, reference.table())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_status<E: std::fmt::Display>(err: E) -> Status {
+    Status::internal(err.to_string())
+}
+
+fn flight_info_for_schema(
+    schema: &Schema,
+    descriptor: FlightDescriptor,
+    ticket: Ticket,
+) -> Result<FlightInfo, Status> {
+    FlightInfo::new()
+        .try_with_schema(schema)
+        .map_err(to_status)
+        .map(|info| {
+            info.with_descriptor(descriptor)
+                .with_endpoint(FlightEndpoint::new().with_ticket(ticket))
+                .with_total_records(-1)
+                .with_total_bytes(-1)
+        })
+}
+
+/// Encodes a single [`RecordBatch`] as the [`FlightData`] stream expected from a `do_get_*`
+/// handler.
+fn record_batch_stream(batch: RecordBatch) -> TonicStream<arrow_flight::FlightData> {
+    let stream = FlightDataEncoderBuilder::new()
+        .build(futures::stream::once(async { Ok(batch) }))
+        .map_err(Status::from);
+    Box::pin(stream)
+}
+
+fn token_from<T>(request: &Request<T>) -> Result<String, Status> {
+    request
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.to_string())
+        .ok_or_else(|| Status::unauthenticated("missing bearer token from handshake"))
+}
+
+#[tonic::async_trait]
+impl FlightSqlService for IcebergFlightSqlService {
+    type FlightService = IcebergFlightSqlService;
+
+    async fn do_handshake(
+        &self,
+        _request: Request<Streaming<arrow_flight::HandshakeRequest>>,
+    ) -> Result<Response<TonicStream<arrow_flight::HandshakeResponse>>, Status> {
+        let token = Uuid::new_v4().to_string();
+        self.session_for(&token);
+        // Flight SQL clients pick the bearer token up from the `authorization` response header.
+        let authorization: tonic::metadata::MetadataValue<tonic::metadata::Ascii> =
+            format!("Bearer {token}").parse().map_err(to_status)?;
+        let response = arrow_flight::HandshakeResponse {
+            protocol_version: 0,
+            payload: token.into_bytes().into(),
+        };
+        let stream = futures::stream::iter(vec![Ok(response)]);
+        let mut response: Response<TonicStream<arrow_flight::HandshakeResponse>> =
+            Response::new(Box::pin(stream));
+        response.metadata_mut().insert("authorization", authorization);
+        Ok(response)
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let token = token_from(&request)?;
+        let ctx = self.session_for(&token);
+        self.register_referenced_tables(&ctx, &query.query).await?;
+
+        let df = ctx.sql(&query.query).await.map_err(to_status)?;
+        let schema = Schema::from(df.schema());
+
+        let handle = Uuid::new_v4().to_string();
+        self.statements.insert(handle.clone(), query.query.clone());
+
+        let ticket_query = TicketStatementQuery {
+            statement_handle: handle.into_bytes().into(),
+        };
+        let ticket = Ticket::new(ticket_query.as_any().encode_to_vec());
+        let info = flight_info_for_schema(&schema, request.into_inner(), ticket)?;
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: TicketStatementQuery,
+        request: Request<Ticket>,
+    ) -> Result<Response<TonicStream<arrow_flight::FlightData>>, Status> {
+        let token = token_from(&request)?;
+        let ctx = self.session_for(&token);
+        let handle = String::from_utf8(ticket.statement_handle.to_vec()).map_err(to_status)?;
+        // Each handle is single-use: `get_flight_info_statement` mints a fresh one per call, so
+        // nothing else will ever look this one up again once it's served here.
+        let (_, query) = self
+            .statements
+            .remove(&handle)
+            .ok_or_else(|| Status::not_found("unknown statement handle"))?;
+
+        let df = ctx.sql(&query).await.map_err(to_status)?;
+        let batches = df.execute_stream().await.map_err(to_status)?;
+        let stream = FlightDataEncoderBuilder::new()
+            .build(batches.map_err(to_status))
+            .map_err(Status::from);
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = Schema::new(vec![Field::new("catalog_name", DataType::Utf8, false)]);
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        let info = flight_info_for_schema(&schema, request.into_inner(), ticket)?;
+        Ok(Response::new(info))
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = Schema::new(vec![
+            Field::new("catalog_name", DataType::Utf8, true),
+            Field::new("db_schema_name", DataType::Utf8, true),
+        ]);
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        let info = flight_info_for_schema(&schema, request.into_inner(), ticket)?;
+        Ok(Response::new(info))
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = Schema::new(vec![
+            Field::new("db_schema_name", DataType::Utf8, true),
+            Field::new("table_name", DataType::Utf8, false),
+        ]);
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        let info = flight_info_for_schema(&schema, request.into_inner(), ticket)?;
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        _query: CommandGetCatalogs,
+        _request: Request<Ticket>,
+    ) -> Result<Response<TonicStream<arrow_flight::FlightData>>, Status> {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "catalog_name",
+            DataType::Utf8,
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec![CATALOG_NAME]))],
+        )
+        .map_err(to_status)?;
+        Ok(Response::new(record_batch_stream(batch)))
+    }
+
+    async fn do_get_schemas(
+        &self,
+        _query: CommandGetDbSchemas,
+        _request: Request<Ticket>,
+    ) -> Result<Response<TonicStream<arrow_flight::FlightData>>, Status> {
+        let namespaces = self.list_namespaces().await?;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("catalog_name", DataType::Utf8, true),
+            Field::new("db_schema_name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec![
+                    CATALOG_NAME;
+                    namespaces.len()
+                ])),
+                Arc::new(StringArray::from(namespaces)),
+            ],
+        )
+        .map_err(to_status)?;
+        Ok(Response::new(record_batch_stream(batch)))
+    }
+
+    async fn do_get_tables(
+        &self,
+        _query: CommandGetTables,
+        _request: Request<Ticket>,
+    ) -> Result<Response<TonicStream<arrow_flight::FlightData>>, Status> {
+        let namespaces = self.list_namespaces().await?;
+        let mut tables = Vec::new();
+        for namespace in namespaces {
+            tables.extend(self.list_tables(&namespace).await?);
+        }
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("db_schema_name", DataType::Utf8, true),
+            Field::new("table_name", DataType::Utf8, false),
+        ]));
+        let (db_schema_names, table_names): (Vec<_>, Vec<_>) = tables.into_iter().unzip();
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(db_schema_names)),
+                Arc::new(StringArray::from(table_names)),
+            ],
+        )
+        .map_err(to_status)?;
+        Ok(Response::new(record_batch_stream(batch)))
+    }
+
+    async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
+}
@@ -23,8 +23,10 @@ use datafusion::arrow::datatypes::{DataType, Field, Schema};
 use datafusion::assert_batches_eq;
 use datafusion::catalog::TableProvider;
 use datafusion::error::DataFusionError;
+use datafusion::physical_plan::displayable;
 use datafusion::prelude::SessionContext;
-use iceberg::{Catalog, TableIdent};
+use iceberg::spec::{NestedField, PrimitiveType, Schema as IcebergSchema, Type};
+use iceberg::{Catalog, NamespaceIdent, TableCreation, TableIdent};
 use iceberg_catalog_rest::RestCatalog;
 use iceberg_datafusion::IcebergTableProvider;
 use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
@@ -195,16 +197,258 @@ async fn test_pyiceberg_types() -> Result<(), DataFusionError> {
     ];
     assert_batches_eq!(expected, &batches);
 
-    // TODO: this isn't OK, and should be fixed with https://github.com/apache/iceberg-rust/issues/813
-    let err = ctx
+    let batches = ctx
         .sql("SELECT cdecimal128 FROM types_table WHERE cint16 <= 2")
         .await?
         .collect()
+        .await?;
+    let expected = [
+        "+-------------+",
+        "| cdecimal128 |",
+        "+-------------+",
+        "| 0.00        |",
+        "| 0.01        |",
+        "| 0.02        |",
+        "+-------------+",
+    ];
+    assert_batches_eq!(expected, &batches);
+
+    Ok(())
+}
+
+/// Creates an empty two-column (`id`, `value`) table with `id` as the only field, so tests can
+/// exercise writes without disturbing the shared read-only fixture tables.
+async fn create_scratch_table(rest_catalog: &RestCatalog, name: &str) -> iceberg::table::Table {
+    let schema = IcebergSchema::builder()
+        .with_fields(vec![
+            Arc::new(NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Int),
+            )),
+            Arc::new(NestedField::required(
+                2,
+                "value",
+                Type::Primitive(PrimitiveType::Int),
+            )),
+        ])
+        .build()
+        .unwrap();
+    let creation = TableCreation::builder()
+        .name(name.to_string())
+        .schema(schema)
+        .build();
+    rest_catalog
+        .create_table(&NamespaceIdent::from_strs(["default"]).unwrap(), creation)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_predicate_pushdown_prunes_scan() -> Result<(), DataFusionError> {
+    let fixture = get_shared_containers();
+    let rest_catalog = RestCatalog::new(fixture.catalog_config.clone());
+
+    let table = rest_catalog
+        .load_table(&TableIdent::from_strs(["default", "types_test_pyiceberg"]).unwrap())
         .await
-        .unwrap_err();
-    assert!(err
-        .to_string()
-        .contains("Invalid comparison operation: Int16 <= Int32"));
+        .unwrap();
+
+    let ctx = SessionContext::new();
+    let table_provider = Arc::new(
+        IcebergTableProvider::try_new_from_table(table)
+            .await
+            .unwrap(),
+    );
+    ctx.register_table("types_table", table_provider)?;
+
+    // `cint32 > 0` translates losslessly into an Iceberg `Predicate`, so it should be pushed all
+    // the way into the `IcebergTableScan` (pruning manifests/row-groups before any bytes are
+    // read) rather than merely re-checked by a `FilterExec` wrapped around the scan.
+    let plan = ctx
+        .sql("SELECT cint32 FROM types_table WHERE cint32 > 0")
+        .await?
+        .create_physical_plan()
+        .await?;
+    let explain = displayable(plan.as_ref()).indent(true).to_string();
+    assert!(
+        explain.contains("predicate=Some"),
+        "expected the filter to be pushed into the Iceberg scan, got:\n{explain}"
+    );
+    assert!(
+        !explain.contains("FilterExec"),
+        "a fully pushed-down predicate should not also need a FilterExec, got:\n{explain}"
+    );
+
+    let batches = ctx
+        .sql("SELECT cint32 FROM types_table WHERE cint32 > 0 ORDER BY cint32 LIMIT 2")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+--------+", "| cint32 |", "+--------+", "| 1      |", "| 2      |", "+--------+",
+    ];
+    assert_batches_eq!(expected, &batches);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_merge_replaces_existing_primary_key() -> Result<(), DataFusionError> {
+    let fixture = get_shared_containers();
+    let rest_catalog = RestCatalog::new(fixture.catalog_config.clone());
+
+    let table = create_scratch_table(&rest_catalog, "merge_replace_test").await;
+    let table_ident = table.identifier().clone();
+
+    let ctx = SessionContext::new();
+    let table_provider = Arc::new(
+        IcebergTableProvider::try_new_from_table(table)
+            .await
+            .unwrap()
+            .with_primary_key(vec!["id".to_string()]),
+    );
+    ctx.register_table("merge_table", table_provider)?;
+
+    ctx.sql("INSERT INTO merge_table VALUES (1, 10)")
+        .await?
+        .collect()
+        .await?;
+    // Same key, different value: this must replace the existing row (one equality delete plus
+    // one insert), not merely append a second row sharing the same key.
+    ctx.sql("INSERT INTO merge_table VALUES (1, 20)")
+        .await?
+        .collect()
+        .await?;
+    // Same key, same value: a genuine no-op that shouldn't write anything at all.
+    ctx.sql("INSERT INTO merge_table VALUES (1, 20)")
+        .await?
+        .collect()
+        .await?;
+
+    let reloaded = rest_catalog.load_table(&table_ident).await.unwrap();
+    let reloaded_provider = Arc::new(
+        IcebergTableProvider::try_new_from_table(reloaded)
+            .await
+            .unwrap(),
+    );
+    ctx.deregister_table("merge_table")?;
+    ctx.register_table("merge_table", reloaded_provider)?;
+
+    let batches = ctx
+        .sql("SELECT id, value FROM merge_table ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+-------+", "| id | value |", "+----+-------+", "| 1  | 20    |", "+----+-------+",
+    ];
+    assert_batches_eq!(expected, &batches);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_time_travel_sees_prior_snapshot() -> Result<(), DataFusionError> {
+    let fixture = get_shared_containers();
+    let rest_catalog = RestCatalog::new(fixture.catalog_config.clone());
+
+    let table = create_scratch_table(&rest_catalog, "time_travel_test").await;
+    let table_ident = table.identifier().clone();
+
+    let ctx = SessionContext::new();
+    let table_provider = Arc::new(
+        IcebergTableProvider::try_new_from_table(table)
+            .await
+            .unwrap()
+            .with_primary_key(vec!["id".to_string()]),
+    );
+    ctx.register_table("time_travel_table", table_provider)?;
+
+    ctx.sql("INSERT INTO time_travel_table VALUES (1, 10)")
+        .await?
+        .collect()
+        .await?;
+    let first_snapshot_table = rest_catalog.load_table(&table_ident).await.unwrap();
+    let first_snapshot_id = first_snapshot_table
+        .metadata()
+        .current_snapshot()
+        .unwrap()
+        .snapshot_id();
+    let first_snapshot_timestamp_ms = first_snapshot_table
+        .metadata()
+        .snapshots_log()
+        .iter()
+        .find(|entry| entry.snapshot_id == first_snapshot_id)
+        .unwrap()
+        .timestamp_ms;
+
+    ctx.deregister_table("time_travel_table")?;
+    let current_provider = Arc::new(
+        IcebergTableProvider::try_new_from_table(first_snapshot_table)
+            .await
+            .unwrap()
+            .with_primary_key(vec!["id".to_string()]),
+    );
+    ctx.register_table("time_travel_table", current_provider)?;
+    ctx.sql("INSERT INTO time_travel_table VALUES (2, 20)")
+        .await?
+        .collect()
+        .await?;
+    ctx.deregister_table("time_travel_table")?;
+
+    let latest_table = rest_catalog.load_table(&table_ident).await.unwrap();
+
+    let snapshot_provider = Arc::new(
+        IcebergTableProvider::try_new_at_snapshot(latest_table.clone(), first_snapshot_id)
+            .await
+            .unwrap(),
+    );
+    ctx.register_table("as_of_snapshot", snapshot_provider)?;
+    let batches = ctx
+        .sql("SELECT id, value FROM as_of_snapshot ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+-------+", "| id | value |", "+----+-------+", "| 1  | 10    |", "+----+-------+",
+    ];
+    assert_batches_eq!(expected, &batches);
+    ctx.deregister_table("as_of_snapshot")?;
+
+    let as_of_provider = Arc::new(
+        IcebergTableProvider::try_new_as_of(latest_table.clone(), first_snapshot_timestamp_ms)
+            .await
+            .unwrap(),
+    );
+    ctx.register_table("as_of_timestamp", as_of_provider)?;
+    let batches = ctx
+        .sql("SELECT id, value FROM as_of_timestamp ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    assert_batches_eq!(expected, &batches);
+
+    let latest_provider = Arc::new(
+        IcebergTableProvider::try_new_from_table(latest_table)
+            .await
+            .unwrap(),
+    );
+    ctx.register_table("latest", latest_provider)?;
+    let batches = ctx
+        .sql("SELECT id, value FROM latest ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+-------+",
+        "| id | value |",
+        "+----+-------+",
+        "| 1  | 10    |",
+        "| 2  | 20    |",
+        "+----+-------+",
+    ];
+    assert_batches_eq!(expected, &batches);
 
     Ok(())
 }
@@ -0,0 +1,118 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Coerces comparisons between an `Int32` Iceberg column and a narrower integer literal or
+//! expression by inserting an explicit `CAST` of the narrower side to `Int32`.
+//!
+//! PyIceberg-written tables store `int8`/`int16` columns as Arrow `Int32` in the schema
+//! [`IcebergTableProvider::schema`](crate::table::IcebergTableProvider::schema) reports, but the
+//! underlying Parquet files carry a narrower logical annotation, which the Arrow reader honours
+//! when no output schema override is given. A predicate such as `cint16 <= 2`, where the
+//! literal `2` is inferred by DataFusion as `Int16` to match the physical column encoding, then
+//! fails DataFusion's comparison kernel with `Invalid comparison operation: Int16 <= Int32`
+//! (see <https://github.com/apache/iceberg-rust/issues/813>). Rewriting the filter before it
+//! reaches the scan sidesteps the mismatch entirely.
+
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::tree_node::{Transformed, TreeNode};
+use datafusion::common::DFSchema;
+use datafusion::error::Result as DFResult;
+use datafusion::logical_expr::{BinaryExpr, Expr, Operator};
+
+/// Returns `true` for the narrow signed integer types that PyIceberg's Parquet files may encode
+/// `int8`/`int16` columns with.
+fn is_narrow_integer(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Int8 | DataType::Int16)
+}
+
+/// If `expr` resolves in `schema` to an integer type narrower than `Int32`, wraps it in a
+/// `CAST(.. AS Int32)`; otherwise returns it unchanged.
+fn cast_narrow_side(expr: Expr, schema: &DFSchema) -> DFResult<Expr> {
+    let data_type = expr.get_type(schema)?;
+    if is_narrow_integer(&data_type) {
+        Ok(Expr::Cast(datafusion::logical_expr::Cast::new(
+            Box::new(expr),
+            DataType::Int32,
+        )))
+    } else {
+        Ok(expr)
+    }
+}
+
+/// Returns `true` if `expr` is a reference to a column whose type in `schema` is `Int32`.
+fn is_int32_column(expr: &Expr, schema: &DFSchema) -> bool {
+    matches!(expr, Expr::Column(_)) && matches!(expr.get_type(schema), Ok(DataType::Int32))
+}
+
+const COMPARISON_OPERATORS: &[Operator] = &[
+    Operator::Eq,
+    Operator::NotEq,
+    Operator::Lt,
+    Operator::LtEq,
+    Operator::Gt,
+    Operator::GtEq,
+];
+
+/// Rewrites every comparison in `filters` between an `Int32` column and a narrower integer
+/// literal/expression, inserting a `CAST` of the narrower side to `Int32` so DataFusion's
+/// comparison kernels accept it. Non-integer comparisons are left untouched.
+pub(crate) fn coerce_integer_comparisons(
+    filters: &[Expr],
+    schema: &DFSchema,
+) -> DFResult<Vec<Expr>> {
+    filters
+        .iter()
+        .cloned()
+        .map(|expr| coerce_expr(expr, schema))
+        .collect()
+}
+
+fn coerce_expr(expr: Expr, schema: &DFSchema) -> DFResult<Expr> {
+    expr.transform_up(|expr| match expr {
+        Expr::BinaryExpr(BinaryExpr { left, right, op }) if COMPARISON_OPERATORS.contains(&op) => {
+            let (left, right) = if is_int32_column(&left, schema) {
+                (left, Box::new(cast_narrow_side(*right, schema)?))
+            } else if is_int32_column(&right, schema) {
+                (Box::new(cast_narrow_side(*left, schema)?), right)
+            } else {
+                (left, right)
+            };
+            Ok(Transformed::yes(Expr::BinaryExpr(BinaryExpr {
+                left,
+                op,
+                right,
+            })))
+        }
+        Expr::Between(between) if is_int32_column(&between.expr, schema) => {
+            let mut between = between;
+            between.low = Box::new(cast_narrow_side(*between.low, schema)?);
+            between.high = Box::new(cast_narrow_side(*between.high, schema)?);
+            Ok(Transformed::yes(Expr::Between(between)))
+        }
+        Expr::InList(in_list) if is_int32_column(&in_list.expr, schema) => {
+            let mut in_list = in_list;
+            in_list.list = in_list
+                .list
+                .into_iter()
+                .map(|item| cast_narrow_side(item, schema))
+                .collect::<DFResult<Vec<_>>>()?;
+            Ok(Transformed::yes(Expr::InList(in_list)))
+        }
+        other => Ok(Transformed::no(other)),
+    })
+    .map(|transformed| transformed.data)
+}
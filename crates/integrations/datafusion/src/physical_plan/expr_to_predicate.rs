@@ -0,0 +1,121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Translates DataFusion [`Expr`]s into Iceberg [`Predicate`]s so they can be pushed into the
+//! [`TableScanBuilder`](iceberg::scan::TableScanBuilder), letting manifest/partition pruning and
+//! row-group skipping happen before any bytes are read, rather than after DataFusion has
+//! materialized every row.
+//!
+//! Only expressions that translate losslessly are accepted; anything else causes translation to
+//! fail and the caller falls back to applying that filter itself.
+
+use datafusion::logical_expr::{BinaryExpr, Expr, Operator};
+use datafusion::scalar::ScalarValue;
+use iceberg::expr::{Predicate, Reference};
+use iceberg::spec::Datum;
+
+/// Returns `Some(predicate)` if `expr` translates losslessly into an Iceberg [`Predicate`],
+/// `None` otherwise.
+pub(crate) fn expr_to_predicate(expr: &Expr) -> Option<Predicate> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => match op {
+            Operator::And => {
+                Some(expr_to_predicate(left)?.and(expr_to_predicate(right)?))
+            }
+            Operator::Or => Some(expr_to_predicate(left)?.or(expr_to_predicate(right)?)),
+            Operator::Eq
+            | Operator::NotEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Gt
+            | Operator::GtEq => column_literal_predicate(left, *op, right)
+                .or_else(|| column_literal_predicate(right, flip(*op), left)),
+            _ => None,
+        },
+        Expr::IsNull(inner) => column_name(inner).map(|name| Reference::new(name).is_null()),
+        Expr::IsNotNull(inner) => {
+            column_name(inner).map(|name| Reference::new(name).is_not_null())
+        }
+        Expr::InList(in_list) if !in_list.negated => {
+            let name = column_name(&in_list.expr)?;
+            let datums = in_list
+                .list
+                .iter()
+                .map(expr_to_datum)
+                .collect::<Option<Vec<_>>>()?;
+            Some(Reference::new(name).is_in(datums))
+        }
+        Expr::Not(inner) => expr_to_predicate(inner).map(Predicate::negate),
+        _ => None,
+    }
+}
+
+fn flip(op: Operator) -> Operator {
+    match op {
+        Operator::Lt => Operator::Gt,
+        Operator::LtEq => Operator::GtEq,
+        Operator::Gt => Operator::Lt,
+        Operator::GtEq => Operator::LtEq,
+        other => other,
+    }
+}
+
+fn column_literal_predicate(column: &Expr, op: Operator, literal: &Expr) -> Option<Predicate> {
+    let name = column_name(column)?;
+    let datum = expr_to_datum(literal)?;
+    let reference = Reference::new(name);
+    Some(match op {
+        Operator::Eq => reference.equal_to(datum),
+        Operator::NotEq => reference.not_equal_to(datum),
+        Operator::Lt => reference.less_than(datum),
+        Operator::LtEq => reference.less_than_or_equal_to(datum),
+        Operator::Gt => reference.greater_than(datum),
+        Operator::GtEq => reference.greater_than_or_equal_to(datum),
+        _ => return None,
+    })
+}
+
+fn column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Column(column) => Some(column.name.clone()),
+        _ => None,
+    }
+}
+
+/// Maps an Arrow literal [`Expr`] back to an Iceberg [`Datum`], using the same primitive type
+/// mapping exercised by the schema conversion in [`crate::schema`].
+fn expr_to_datum(expr: &Expr) -> Option<Datum> {
+    let Expr::Literal(scalar) = expr else {
+        return None;
+    };
+    match scalar {
+        ScalarValue::Boolean(Some(v)) => Some(Datum::bool(*v)),
+        ScalarValue::Int8(Some(v)) => Some(Datum::int(*v as i32)),
+        ScalarValue::Int16(Some(v)) => Some(Datum::int(*v as i32)),
+        ScalarValue::Int32(Some(v)) => Some(Datum::int(*v)),
+        ScalarValue::Int64(Some(v)) => Some(Datum::long(*v)),
+        ScalarValue::Float32(Some(v)) => Some(Datum::float(*v)),
+        ScalarValue::Float64(Some(v)) => Some(Datum::double(*v)),
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+            Some(Datum::string(v.clone()))
+        }
+        ScalarValue::Binary(Some(v)) | ScalarValue::LargeBinary(Some(v)) => {
+            Some(Datum::binary(v.clone()))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,350 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`ExecutionPlan`] behind [`IcebergTableProvider::insert_into`](crate::table::IcebergTableProvider::insert_into).
+//!
+//! Incoming rows are diffed against the table's current snapshot on `primary_key` columns: the
+//! current snapshot is read back into an in-memory `key -> row` map, and any incoming row whose
+//! key maps to an identical existing row is dropped before writing. The remaining rows are handed
+//! to an [`EqualityDeltaWriter`](iceberg::writer::base_writer::equality_delta_writer::EqualityDeltaWriter),
+//! which deletes any existing row sharing the key and inserts the new one -- a changed key emits
+//! an equality delete for the old row plus an insert for the new one, and a newly-seen key emits
+//! only an insert. The resulting data and equality-delete files are written with the same
+//! field-id metadata the read path expects, then committed as a single new snapshot through a
+//! [`Transaction`] so the merge is atomic.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{BooleanArray, RecordBatch};
+use datafusion::arrow::compute::filter_record_batch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::common::ScalarValue;
+use datafusion::error::{DataFusionError, Result as DFResult};
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning, PlanProperties,
+};
+use futures::{StreamExt, TryStreamExt};
+use iceberg::table::Table;
+use iceberg::transaction::Transaction;
+use iceberg::writer::base_writer::equality_delta_writer::{
+    EqualityDeltaWriterBuilder, DELETE_OP, INSERT_OP,
+};
+use iceberg::writer::file_writer::location_generator::DefaultLocationGenerator;
+use iceberg::writer::file_writer::ParquetWriterBuilder;
+use iceberg::writer::{IcebergWriter, IcebergWriterBuilder};
+use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+
+use crate::error::to_datafusion_error;
+
+/// Converts any displayable error into an [`iceberg::Error`], for error worlds (Arrow/DataFusion)
+/// that don't otherwise cross the boundary back into `iceberg::Result`.
+fn to_iceberg_error(error: impl std::fmt::Display) -> iceberg::Error {
+    iceberg::Error::new(iceberg::ErrorKind::Unexpected, error.to_string())
+}
+
+/// Commits `input`'s rows into `table` as insert/update/delete operations, keyed by
+/// `primary_key_field_ids`, and returns a single-row count [`RecordBatch`] the way
+/// [`TableProvider::insert_into`](datafusion::catalog::TableProvider::insert_into) callers expect.
+#[derive(Debug)]
+pub(crate) struct IcebergMergeExec {
+    table: Table,
+    primary_key_field_ids: Vec<i32>,
+    input: Arc<dyn ExecutionPlan>,
+    plan_properties: PlanProperties,
+}
+
+impl IcebergMergeExec {
+    pub(crate) fn new(
+        table: Table,
+        primary_key_field_ids: Vec<i32>,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Self {
+        let schema = count_schema();
+        let plan_properties = PlanProperties::new(
+            EquivalenceProperties::new(schema),
+            Partitioning::UnknownPartitioning(1),
+            ExecutionMode::Bounded,
+        );
+        Self {
+            table,
+            primary_key_field_ids,
+            input,
+            plan_properties,
+        }
+    }
+}
+
+/// The one-column `count` schema DataFusion expects an `insert_into` execution plan to report.
+fn count_schema() -> SchemaRef {
+    Arc::new(datafusion::arrow::datatypes::Schema::new(vec![
+        datafusion::arrow::datatypes::Field::new(
+            "count",
+            datafusion::arrow::datatypes::DataType::UInt64,
+            false,
+        ),
+    ]))
+}
+
+impl DisplayAs for IcebergMergeExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "IcebergMergeExec: table={}, keys={:?}",
+            self.table.identifier(),
+            self.primary_key_field_ids
+        )
+    }
+}
+
+impl ExecutionPlan for IcebergMergeExec {
+    fn name(&self) -> &str {
+        "IcebergMergeExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.plan_properties
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    /// All input rows go through one merge, so the input is coalesced into a single partition.
+    fn required_input_distribution(&self) -> Vec<datafusion::physical_plan::Distribution> {
+        vec![datafusion::physical_plan::Distribution::SinglePartition]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DFResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::new(
+            self.table.clone(),
+            self.primary_key_field_ids.clone(),
+            children.remove(0),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DFResult<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, context)?;
+        let table = self.table.clone();
+        let primary_key_field_ids = self.primary_key_field_ids.clone();
+
+        let stream = futures::stream::once(async move {
+            run_merge(table, primary_key_field_ids, input)
+                .await
+                .map_err(to_datafusion_error)
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            count_schema(),
+            stream,
+        )))
+    }
+}
+
+/// Diffs `input` against `table`'s current snapshot on `primary_key_field_ids`, writes the
+/// resulting data/equality-delete files, commits a new snapshot, and returns the row count that
+/// was written.
+async fn run_merge(
+    table: Table,
+    primary_key_field_ids: Vec<i32>,
+    mut input: SendableRecordBatchStream,
+) -> iceberg::Result<impl futures::Stream<Item = iceberg::Result<RecordBatch>>> {
+    // `table` is the handle the provider was built with; reload it so that the diff and the
+    // commit are made against the snapshot that is current now, after any earlier inserts.
+    let table = table.catalog().load_table(table.identifier()).await?;
+    let schema = table.metadata().current_schema().clone();
+    let arrow_schema =
+        crate::schema::iceberg_schema_to_arrow_schema(&schema).map_err(to_iceberg_error)?;
+    let key_indices = primary_key_column_indices(&arrow_schema, &primary_key_field_ids)?;
+    let existing_rows = existing_rows_by_key(&table, &key_indices).await?;
+
+    let location_generator = DefaultLocationGenerator::new(table.metadata().clone())?;
+    let parquet_writer_builder = ParquetWriterBuilder::new(
+        Default::default(),
+        schema.clone(),
+        table.file_io().clone(),
+        location_generator,
+        iceberg::writer::file_writer::location_generator::DefaultFileNameGenerator::new(
+            "merge".to_string(),
+            None,
+            iceberg::spec::DataFileFormat::Parquet,
+        ),
+    );
+
+    let delta_writer_builder = EqualityDeltaWriterBuilder::new(
+        parquet_writer_builder.clone(),
+        parquet_writer_builder,
+        primary_key_field_ids.clone(),
+    );
+    let mut writer = delta_writer_builder.build().await?;
+
+    let mut row_count: u64 = 0;
+    while let Some(batch) = input.next().await {
+        let batch = batch.map_err(to_iceberg_error)?;
+        let diff = diff_rows(&batch, &key_indices, &existing_rows)?;
+
+        // Rows that replace an existing key need an equality delete for the old row, in
+        // addition to the insert below -- otherwise the old and new rows both survive under the
+        // same primary key.
+        let replaced_batch = filter_record_batch(&batch, &diff.replaces_existing_key)
+            .map_err(to_iceberg_error)?;
+        if replaced_batch.num_rows() > 0 {
+            writer.write(DELETE_OP, replaced_batch).await?;
+        }
+
+        let written_batch = filter_record_batch(&batch, &diff.needs_write).map_err(to_iceberg_error)?;
+        if written_batch.num_rows() == 0 {
+            continue;
+        }
+        row_count += written_batch.num_rows() as u64;
+        writer.write(INSERT_OP, written_batch).await?;
+    }
+
+    let data_files = writer.close().await?;
+
+    // An input made only of no-op rows writes no files, and no snapshot is committed for it.
+    if !data_files.is_empty() {
+        let transaction = Transaction::new(&table);
+        let transaction = transaction.merge_append(data_files)?;
+        transaction.commit(table.catalog()).await?;
+    }
+
+    Ok(futures::stream::once(async move {
+        Ok(RecordBatch::try_new(
+            count_schema(),
+            vec![Arc::new(datafusion::arrow::array::UInt64Array::from(vec![
+                row_count,
+            ]))],
+        )
+        .expect("count batch matches count_schema"))
+    }))
+}
+
+/// Maps each of `field_ids` to its column index in `schema`, using the Iceberg field-id metadata
+/// every field carries (see [`crate::schema::iceberg_schema_to_arrow_schema`]).
+fn primary_key_column_indices(
+    schema: &SchemaRef,
+    field_ids: &[i32],
+) -> iceberg::Result<Vec<usize>> {
+    field_ids
+        .iter()
+        .map(|id| {
+            schema
+                .fields()
+                .iter()
+                .position(|field| {
+                    field
+                        .metadata()
+                        .get(PARQUET_FIELD_ID_META_KEY)
+                        .and_then(|v| v.parse::<i32>().ok())
+                        == Some(*id)
+                })
+                .ok_or_else(|| {
+                    to_iceberg_error(format!(
+                        "primary key field id {id} not found in table schema"
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Reads every row of `table`'s current snapshot, keyed by the values at `key_indices`, so
+/// incoming rows can be diffed against it without re-reading the table per row.
+async fn existing_rows_by_key(
+    table: &Table,
+    key_indices: &[usize],
+) -> iceberg::Result<HashMap<Vec<ScalarValue>, Vec<ScalarValue>>> {
+    let scan = table.scan().build()?;
+    let mut stream = scan.to_arrow().await?;
+    let mut rows = HashMap::new();
+    while let Some(batch) = stream.try_next().await? {
+        for row in 0..batch.num_rows() {
+            let values = row_scalars(&batch, row)?;
+            let key = key_indices.iter().map(|&i| values[i].clone()).collect();
+            rows.insert(key, values);
+        }
+    }
+    Ok(rows)
+}
+
+/// Reads every column's value at `row` out of `batch` as a [`ScalarValue`].
+fn row_scalars(batch: &RecordBatch, row: usize) -> iceberg::Result<Vec<ScalarValue>> {
+    batch
+        .columns()
+        .iter()
+        .map(|column| ScalarValue::try_from_array(column, row).map_err(to_iceberg_error))
+        .collect()
+}
+
+/// Per-row classification of `batch` against `existing_rows`.
+struct RowDiff {
+    /// `true` for every row that is a new key or whose key maps to an existing row with
+    /// different values -- i.e. every row that isn't a complete no-op and must be written.
+    needs_write: BooleanArray,
+    /// `true` for every row whose key maps to an existing row with different values, and which
+    /// therefore also needs an equality delete for that old row.
+    replaces_existing_key: BooleanArray,
+}
+
+/// Classifies every row of `batch` as a no-op, a new key, or a replacement for an existing key,
+/// by looking its primary key up in `existing_rows`.
+fn diff_rows(
+    batch: &RecordBatch,
+    key_indices: &[usize],
+    existing_rows: &HashMap<Vec<ScalarValue>, Vec<ScalarValue>>,
+) -> iceberg::Result<RowDiff> {
+    let mut needs_write = Vec::with_capacity(batch.num_rows());
+    let mut replaces_existing_key = Vec::with_capacity(batch.num_rows());
+    for row in 0..batch.num_rows() {
+        let values = row_scalars(batch, row)?;
+        let key: Vec<ScalarValue> = key_indices.iter().map(|&i| values[i].clone()).collect();
+        match existing_rows.get(&key) {
+            Some(existing) if existing == &values => {
+                needs_write.push(false);
+                replaces_existing_key.push(false);
+            }
+            Some(_) => {
+                needs_write.push(true);
+                replaces_existing_key.push(true);
+            }
+            None => {
+                needs_write.push(true);
+                replaces_existing_key.push(false);
+            }
+        }
+    }
+    Ok(RowDiff {
+        needs_write: BooleanArray::from(needs_write),
+        replaces_existing_key: BooleanArray::from(replaces_existing_key),
+    })
+}
@@ -0,0 +1,72 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of Iceberg schemas into the Arrow schemas that DataFusion operates on.
+//!
+//! Iceberg's `int` type is a 32-bit integer and Iceberg has no 8/16-bit integer types, so
+//! `int8`/`int16` columns written by engines such as PyIceberg are `int` in the Iceberg schema
+//! and become Arrow `Int32` here, even though their Parquet files may carry a narrower logical
+//! annotation (the filter coercion in `physical_plan::expr_rewrite` deals with that). Every field
+//! in the resulting schema also carries the Iceberg field id in its metadata so that later
+//! pushdown and projection code can map back to the originating Iceberg field.
+
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Field, Schema, SchemaRef};
+use iceberg::spec::Schema as IcebergSchema;
+use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+
+use crate::error::to_datafusion_error;
+
+/// Converts an [`IcebergSchema`] into the [`SchemaRef`] DataFusion sees for a table, mapping
+/// Iceberg `int` to `Int32` and tagging every field with its Iceberg field id.
+pub(crate) fn iceberg_schema_to_arrow_schema(
+    schema: &IcebergSchema,
+) -> datafusion::error::Result<SchemaRef> {
+    let fields = schema
+        .as_struct()
+        .fields()
+        .iter()
+        .map(|field| {
+            let arrow_type =
+                iceberg::arrow::type_to_arrow_type(&field.field_type).map_err(to_datafusion_error)?;
+            Ok(Field::new(&field.name, arrow_type, !field.required)
+                .with_metadata(std::collections::HashMap::from([(
+                    PARQUET_FIELD_ID_META_KEY.to_string(),
+                    field.id.to_string(),
+                )])))
+        })
+        .collect::<datafusion::error::Result<Vec<_>>>()?;
+
+    Ok(Arc::new(Schema::new(fields)))
+}
+
+/// Whether `input` and `table` describe the same columns, ignoring field metadata.
+///
+/// Every field in a table's schema carries Iceberg field-id metadata (see
+/// [`iceberg_schema_to_arrow_schema`]), but a `LogicalPlan` feeding an `INSERT INTO` (e.g. a
+/// `SELECT` from another source, or a `VALUES` list) has no reason to carry that metadata on its
+/// output schema. Comparing schemas with plain `==` would therefore reject realistic inserts, not
+/// just genuine mismatches, so this only compares field names, types and nullability.
+pub(crate) fn schemas_match_ignoring_metadata(input: &Schema, table: &Schema) -> bool {
+    input.fields().len() == table.fields().len()
+        && input.fields().iter().zip(table.fields()).all(|(a, b)| {
+            a.name() == b.name()
+                && a.data_type() == b.data_type()
+                && a.is_nullable() == b.is_nullable()
+        })
+}
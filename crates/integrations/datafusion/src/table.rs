@@ -0,0 +1,316 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::catalog::{Session, TableProvider};
+use datafusion::common::DFSchema;
+use datafusion::datasource::TableType;
+use datafusion::error::{DataFusionError, Result as DFResult};
+use datafusion::logical_expr::dml::InsertOp;
+use datafusion::logical_expr::{Expr, TableProviderFilterPushDown};
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::{ExecutionPlan, PhysicalExpr};
+use iceberg::table::Table;
+use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+
+use crate::physical_plan::{
+    coerce_integer_comparisons, expr_to_predicate, IcebergMergeExec, IcebergTableScan,
+};
+use crate::schema::{iceberg_schema_to_arrow_schema, schemas_match_ignoring_metadata};
+
+/// Resolves the Arrow schema a provider pinned to `snapshot_id` (or the current snapshot, if
+/// `None`) should report, honoring schema evolution across snapshots.
+fn arrow_schema_for_snapshot(table: &Table, snapshot_id: Option<i64>) -> iceberg::Result<SchemaRef> {
+    let iceberg_schema = match snapshot_id {
+        None => table.metadata().current_schema().clone(),
+        Some(snapshot_id) => {
+            let snapshot = table.metadata().snapshot_by_id(snapshot_id).ok_or_else(|| {
+                iceberg::Error::new(
+                    iceberg::ErrorKind::DataInvalid,
+                    format!("no snapshot with id {snapshot_id} in table {}", table.identifier()),
+                )
+            })?;
+            snapshot.schema(table.metadata())?
+        }
+    };
+    iceberg_schema_to_arrow_schema(&iceberg_schema)
+        .map_err(|e| iceberg::Error::new(iceberg::ErrorKind::Unexpected, e.to_string()))
+}
+
+/// Finds the latest snapshot in `table`'s snapshot log whose commit time is at or before
+/// `timestamp_ms`.
+fn snapshot_id_as_of(table: &Table, timestamp_ms: i64) -> iceberg::Result<i64> {
+    table
+        .metadata()
+        .snapshots_log() // chronological (timestamp_ms, snapshot_id) entries
+        .iter()
+        .filter(|entry| entry.timestamp_ms <= timestamp_ms)
+        .max_by_key(|entry| entry.timestamp_ms)
+        .map(|entry| entry.snapshot_id)
+        .ok_or_else(|| {
+            iceberg::Error::new(
+                iceberg::ErrorKind::DataInvalid,
+                format!(
+                    "table {} has no snapshot at or before timestamp {timestamp_ms}",
+                    table.identifier()
+                ),
+            )
+        })
+}
+
+/// A DataFusion [`TableProvider`] backed by an [`iceberg::table::Table`].
+///
+/// Construct one with [`IcebergTableProvider::try_new_from_table`] and register it with a
+/// [`SessionContext`](datafusion::prelude::SessionContext) to run SQL against the table. Call
+/// [`Self::with_primary_key`] before registering it if you also want `INSERT INTO`, which merges
+/// rows on that key; without a primary key, inserts are rejected.
+#[derive(Debug)]
+pub struct IcebergTableProvider {
+    table: Table,
+    schema: SchemaRef,
+    primary_key: Option<Vec<String>>,
+    /// `None` means "scan the table's current snapshot"; `Some` pins every scan to that
+    /// snapshot id, for time-travel queries built by [`Self::try_new_at_snapshot`] and
+    /// [`Self::try_new_as_of`].
+    snapshot_id: Option<i64>,
+}
+
+impl IcebergTableProvider {
+    /// Builds a provider for the current (latest) snapshot of `table`.
+    pub async fn try_new_from_table(table: Table) -> iceberg::Result<Self> {
+        let schema = arrow_schema_for_snapshot(&table, None)?;
+        Ok(Self {
+            table,
+            schema,
+            primary_key: None,
+            snapshot_id: None,
+        })
+    }
+
+    /// Builds a provider pinned to `snapshot_id`, including its schema as of that snapshot
+    /// (schemas can evolve between snapshots, so this may differ from the table's current
+    /// schema).
+    pub async fn try_new_at_snapshot(table: Table, snapshot_id: i64) -> iceberg::Result<Self> {
+        let schema = arrow_schema_for_snapshot(&table, Some(snapshot_id))?;
+        Ok(Self {
+            table,
+            schema,
+            primary_key: None,
+            snapshot_id: Some(snapshot_id),
+        })
+    }
+
+    /// Builds a provider pinned to the latest snapshot whose commit time is at or before
+    /// `timestamp_ms` (milliseconds since the Unix epoch), using the table's snapshot log.
+    /// Returns an error if no such snapshot exists.
+    pub async fn try_new_as_of(table: Table, timestamp_ms: i64) -> iceberg::Result<Self> {
+        let snapshot_id = snapshot_id_as_of(&table, timestamp_ms)?;
+        Self::try_new_at_snapshot(table, snapshot_id).await
+    }
+
+    /// Configures `columns` as the key `INSERT INTO` merges rows on: a row whose key matches an
+    /// existing one replaces it (delete + insert), and a row with an unseen key is inserted.
+    /// Without this, [`Self::insert_into`] rejects writes.
+    pub fn with_primary_key(mut self, columns: Vec<String>) -> Self {
+        self.primary_key = Some(columns);
+        self
+    }
+
+    fn primary_key_field_ids(&self) -> DFResult<Vec<i32>> {
+        let columns = self.primary_key.as_ref().ok_or_else(|| {
+            DataFusionError::Plan(
+                "table has no primary key configured; call IcebergTableProvider::with_primary_key \
+                 before inserting into it"
+                    .to_string(),
+            )
+        })?;
+        columns
+            .iter()
+            .map(|name| {
+                self.schema
+                    .field_with_name(name)
+                    .map_err(|e| DataFusionError::Plan(e.to_string()))
+                    .and_then(|field| {
+                        field
+                            .metadata()
+                            .get(PARQUET_FIELD_ID_META_KEY)
+                            .ok_or_else(|| {
+                                DataFusionError::Plan(format!(
+                                    "primary key column '{name}' is missing Iceberg field-id metadata"
+                                ))
+                            })?
+                            .parse::<i32>()
+                            .map_err(|e| DataFusionError::Plan(e.to_string()))
+                    })
+            })
+            .collect()
+    }
+
+    /// Coerces `filters` for the narrow-vs-`Int32` integer mismatch (see
+    /// [`crate::physical_plan::coerce_integer_comparisons`]), then wraps `input` in a
+    /// [`FilterExec`] evaluating the rewritten predicate.
+    fn wrap_with_coerced_filter(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        filters: &[Expr],
+        df_schema: &DFSchema,
+    ) -> DFResult<Arc<dyn ExecutionPlan>> {
+        if filters.is_empty() {
+            return Ok(input);
+        }
+
+        let rewritten = coerce_integer_comparisons(filters, df_schema)?;
+        let combined = rewritten
+            .into_iter()
+            .reduce(|acc, expr| acc.and(expr))
+            .expect("filters is non-empty");
+        let physical_expr: Arc<dyn PhysicalExpr> =
+            datafusion::physical_expr::create_physical_expr(&combined, df_schema, &Default::default())?;
+        Ok(Arc::new(FilterExec::try_new(physical_expr, input)?))
+    }
+
+    /// Splits `filters` into those that translate losslessly into an Iceberg [`Predicate`] (and
+    /// can therefore be pushed into the [`TableScanBuilder`](iceberg::scan::TableScanBuilder),
+    /// pruning manifests/row-groups before any bytes are read) and the remainder, which are
+    /// still fully evaluated locally via [`Self::wrap_with_coerced_filter`].
+    fn split_pushable_filters(filters: &[Expr]) -> (Option<iceberg::expr::Predicate>, Vec<Expr>) {
+        let mut pushed = None;
+        let mut remaining = Vec::new();
+        for filter in filters {
+            match expr_to_predicate(filter) {
+                Some(predicate) => {
+                    pushed = Some(match pushed.take() {
+                        Some(existing) => existing.and(predicate),
+                        None => predicate,
+                    });
+                }
+                None => remaining.push(filter.clone()),
+            }
+        }
+        (pushed, remaining)
+    }
+}
+
+#[async_trait::async_trait]
+impl TableProvider for IcebergTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> DFResult<Vec<TableProviderFilterPushDown>> {
+        // Filters that translate into an Iceberg `Predicate` are pushed into the `TableScan` and
+        // fully applied there, so DataFusion does not need to re-check them. Everything else is
+        // still coerced and evaluated locally as a best effort (see `wrap_with_coerced_filter`),
+        // but reported as `Inexact` so DataFusion keeps re-checking it too.
+        Ok(filters
+            .iter()
+            .map(|filter| {
+                if expr_to_predicate(filter).is_some() {
+                    TableProviderFilterPushDown::Exact
+                } else {
+                    TableProviderFilterPushDown::Inexact
+                }
+            })
+            .collect())
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DFResult<Arc<dyn ExecutionPlan>> {
+        let _ = state;
+        let projected_schema = match projection {
+            Some(indices) => Arc::new(self.schema.project(indices)?),
+            None => self.schema.clone(),
+        };
+        // `base_scan` (and therefore the `FilterExec` wrapping it below) outputs
+        // `projected_schema`, not the full table schema, so the filter's physical expression
+        // must be built against `projected_schema` too -- otherwise `Column` indices are
+        // resolved positionally against the wrong schema and either panic or read the wrong
+        // column once a projection is pushed down.
+        let projected_df_schema = DFSchema::try_from(projected_schema.as_ref().clone())?;
+
+        let (predicate, remaining_filters) = Self::split_pushable_filters(filters);
+
+        let base_scan: Arc<dyn ExecutionPlan> = Arc::new(IcebergTableScan::new(
+            self.table.clone(),
+            self.snapshot_id,
+            projected_schema,
+            predicate,
+            projection.map(|indices| {
+                indices
+                    .iter()
+                    .map(|i| self.schema.field(*i).name().clone())
+                    .collect()
+            }),
+        ));
+
+        self.wrap_with_coerced_filter(base_scan, &remaining_filters, &projected_df_schema)
+    }
+
+    async fn insert_into(
+        &self,
+        _state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> DFResult<Arc<dyn ExecutionPlan>> {
+        if insert_op != InsertOp::Append {
+            return Err(DataFusionError::NotImplemented(format!(
+                "IcebergTableProvider only supports merge-on-primary-key inserts, not {insert_op:?}"
+            )));
+        }
+        if self.snapshot_id.is_some() {
+            return Err(DataFusionError::Plan(
+                "cannot insert into a time-travel/snapshot-pinned IcebergTableProvider; build \
+                 one with try_new_from_table to write to the table's current snapshot"
+                    .to_string(),
+            ));
+        }
+        if !schemas_match_ignoring_metadata(input.schema().as_ref(), self.schema.as_ref()) {
+            return Err(DataFusionError::Plan(format!(
+                "schema of the rows being inserted does not match the table's schema: \
+                 expected {:?}, got {:?}",
+                self.schema,
+                input.schema()
+            )));
+        }
+
+        let primary_key_field_ids = self.primary_key_field_ids()?;
+        Ok(Arc::new(IcebergMergeExec::new(
+            self.table.clone(),
+            primary_key_field_ids,
+            input,
+        )))
+    }
+}
@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! PyO3 bindings exposing [`IcebergTableProvider`] to Python, so a table loaded through a
+//! [`RestCatalog`] can be registered into a Python `datafusion.SessionContext` and queried with
+//! `datafusion-python`, without writing any Rust.
+//!
+//! The provider crosses the Python/Rust boundary via the `datafusion-ffi`
+//! `__datafusion_table_provider__` `PyCapsule` protocol that `datafusion-python` itself uses for
+//! foreign table providers, rather than re-implementing DataFusion's Python API surface here.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::sync::{Arc, OnceLock};
+
+use datafusion_ffi::table_provider::FFI_TableProvider;
+use iceberg::{Catalog, NamespaceIdent, TableIdent};
+use iceberg_catalog_rest::{RestCatalog, RestCatalogConfig};
+use iceberg_datafusion::IcebergTableProvider;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use tokio::runtime::Runtime;
+
+/// The single Tokio runtime backing every provider this module hands to Python.
+///
+/// It must outlive the `table_provider()` call that builds a given [`PyIcebergTableProvider`]:
+/// `FFI_TableProvider` is handed this runtime's [`Handle`](tokio::runtime::Handle) so that Python
+/// driving `scan()`/`execute()` from a thread with no ambient Tokio reactor still has one to
+/// poll against, which a runtime dropped at the end of `table_provider()` could not provide.
+fn runtime() -> PyResult<&'static Runtime> {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = Runtime::new().map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
+
+fn to_py_err(err: impl std::fmt::Display) -> PyErr {
+    PyRuntimeError::new_err(err.to_string())
+}
+
+/// A handle to a REST-backed Iceberg catalog, constructed from the same configuration keys
+/// (`uri`, `warehouse`, etc.) accepted by [`RestCatalogConfig`].
+#[pyclass(name = "RestCatalog")]
+pub struct PyRestCatalog {
+    config: RestCatalogConfig,
+}
+
+#[pymethods]
+impl PyRestCatalog {
+    #[new]
+    fn new(config: HashMap<String, String>) -> PyResult<Self> {
+        let mut builder = RestCatalogConfig::builder();
+        if let Some(uri) = config.get("uri") {
+            builder = builder.uri(uri.clone());
+        }
+        if let Some(warehouse) = config.get("warehouse") {
+            builder = builder.warehouse(warehouse.clone());
+        }
+        builder = builder.props(config);
+        Ok(Self {
+            config: builder.build(),
+        })
+    }
+}
+
+/// A DataFusion `TableProvider` for an Iceberg table, implementing the
+/// `__datafusion_table_provider__` `PyCapsule` protocol so it can be passed directly to
+/// `datafusion.SessionContext.register_table_provider`.
+#[pyclass(name = "IcebergTableProvider")]
+pub struct PyIcebergTableProvider {
+    inner: Arc<IcebergTableProvider>,
+    runtime: tokio::runtime::Handle,
+}
+
+#[pymethods]
+impl PyIcebergTableProvider {
+    fn __datafusion_table_provider__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let provider =
+            FFI_TableProvider::new(self.inner.clone(), false, Some(self.runtime.clone()));
+        let name = CString::new("datafusion_table_provider").unwrap();
+        PyCapsule::new(py, provider, Some(name))
+    }
+}
+
+/// Loads `namespace.table` from `catalog` and returns a provider that can be registered into a
+/// Python DataFusion `SessionContext`, preserving the Arrow field-id metadata and narrow-integer
+/// widening that [`IcebergTableProvider::schema`] produces on the Rust side.
+#[pyfunction]
+fn table_provider(
+    catalog: &PyRestCatalog,
+    namespace: Vec<String>,
+    table: String,
+) -> PyResult<PyIcebergTableProvider> {
+    let rt = runtime()?;
+    let rest_catalog = RestCatalog::new(catalog.config.clone());
+    let ident = TableIdent::new(
+        NamespaceIdent::from_strs(namespace).map_err(to_py_err)?,
+        table,
+    );
+    let iceberg_table = rt
+        .block_on(rest_catalog.load_table(&ident))
+        .map_err(to_py_err)?;
+    let provider = rt
+        .block_on(IcebergTableProvider::try_new_from_table(iceberg_table))
+        .map_err(to_py_err)?;
+    Ok(PyIcebergTableProvider {
+        inner: Arc::new(provider),
+        runtime: rt.handle().clone(),
+    })
+}
+
+/// The `iceberg_datafusion` Python module.
+#[pymodule]
+fn iceberg_datafusion(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyRestCatalog>()?;
+    m.add_class::<PyIcebergTableProvider>()?;
+    m.add_function(wrap_pyfunction!(table_provider, m)?)?;
+    Ok(())
+}